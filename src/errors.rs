@@ -0,0 +1,31 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::fmt;
+
+/// Internal error types produced by this crate's OPRF/PAKE primitives.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InternalPakeError {
+    /// A group element failed to decode or decompress.
+    PointError,
+    /// A [`crate::oprf::DleqProof`] did not verify against the claimed public key.
+    DleqProofVerificationError,
+    /// [`crate::oprf::combine_oprf_shares`] was called with no key shares to reconstruct from.
+    InsufficientOprfSharesError,
+    /// A batched OPRF call ([`crate::oprf::generate_oprf2_batch`] or
+    /// [`crate::oprf::verify_oprf2_batch`]) was given an empty batch of blinded points.
+    EmptyBatchError,
+    /// [`crate::oprf::verify_oprf2_batch`] was given `points` and `betas` slices of different
+    /// lengths.
+    BatchLengthMismatchError,
+}
+
+impl fmt::Display for InternalPakeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for InternalPakeError {}
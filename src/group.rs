@@ -0,0 +1,98 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::errors::InternalPakeError;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use digest::Digest;
+use generic_array::{
+    typenum::{U32, U64},
+    ArrayLength, GenericArray,
+};
+use rand_core::{CryptoRng, RngCore};
+use std::ops::{Add, Mul, Sub};
+
+/// A prime-order group usable as the setting for the multiplicative-blinding DH-OPRF in
+/// [`crate::oprf`]. Implementors provide the scalar/point arithmetic, a `hash_to_curve` map
+/// from uniform bytes to a group element, and a fixed generator (`base_point`) so that
+/// protocols built on top (e.g. the VOPRF's DLEQ proof) can commit to a public key.
+pub trait Group:
+    Copy
+    + Add<Output = Self>
+    + Mul<Self::Scalar, Output = Self>
+    + for<'a> Mul<&'a Self::Scalar, Output = Self>
+{
+    /// The scalar field this group's points are multiplied by.
+    type Scalar: Copy
+        + PartialEq
+        + Add<Output = Self::Scalar>
+        + Sub<Output = Self::Scalar>
+        + Mul<Self::Scalar, Output = Self::Scalar>
+        + for<'a> Mul<&'a Self::Scalar, Output = Self::Scalar>;
+    /// The length of this group's compressed point encoding, as returned by [`Group::to_arr`].
+    type ElemLen: ArrayLength<u8>;
+    /// The length of the uniform byte string [`Group::hash_to_curve`] expects.
+    type UniformBytesLen: ArrayLength<u8>;
+    /// The length of this group's scalar encoding, as accepted by [`Group::from_scalar_slice`].
+    /// Not assumed to equal `ElemLen` or any other group's scalar length, so callers reducing
+    /// a hash output to a scalar (e.g. for a Fiat-Shamir challenge) can size their window per
+    /// ciphersuite instead of hardcoding 32 bytes.
+    type ScalarLen: ArrayLength<u8>;
+
+    /// Samples a uniformly random scalar, used as a blinding factor.
+    fn random_scalar<R: RngCore + CryptoRng>(rng: &mut R) -> Self::Scalar;
+    /// Computes the multiplicative inverse of a scalar, used to unblind.
+    fn scalar_invert(scalar: &Self::Scalar) -> Self::Scalar;
+    /// Reduces a [`Group::ScalarLen`]-byte string into a scalar modulo the group order.
+    fn from_scalar_slice(
+        bytes: &GenericArray<u8, Self::ScalarLen>,
+    ) -> Result<Self::Scalar, InternalPakeError>;
+    /// Maps a uniform byte string to a group element.
+    fn hash_to_curve(uniform_bytes: &GenericArray<u8, Self::UniformBytesLen>) -> Self;
+    /// Encodes this point to its canonical compressed byte representation.
+    fn to_arr(&self) -> GenericArray<u8, Self::ElemLen>;
+    /// This group's fixed generator, used as the base for the VOPRF's DLEQ proof.
+    fn base_point() -> Self;
+}
+
+impl Group for RistrettoPoint {
+    type Scalar = Scalar;
+    type ElemLen = U32;
+    type UniformBytesLen = U64;
+    type ScalarLen = U32;
+
+    fn random_scalar<R: RngCore + CryptoRng>(rng: &mut R) -> Self::Scalar {
+        Scalar::random(rng)
+    }
+
+    fn scalar_invert(scalar: &Self::Scalar) -> Self::Scalar {
+        scalar.invert()
+    }
+
+    fn from_scalar_slice(bytes: &GenericArray<u8, U32>) -> Result<Self::Scalar, InternalPakeError> {
+        let mut arr = [0u8; 32];
+        arr.copy_from_slice(bytes);
+        Ok(Scalar::from_bytes_mod_order(arr))
+    }
+
+    fn hash_to_curve(uniform_bytes: &GenericArray<u8, Self::UniformBytesLen>) -> Self {
+        // `RistrettoPoint::from_hash` wants a hasher built against the `digest` version
+        // curve25519-dalek is pinned to, which predates the one the rest of this crate's HKDF
+        // plumbing uses. Re-hashing through our own `Sha512` and handing the raw 64 bytes to
+        // `from_uniform_bytes` sidesteps that version mismatch.
+        let mut hasher = sha2::Sha512::new();
+        hasher.update(uniform_bytes);
+        let mut arr = [0u8; 64];
+        arr.copy_from_slice(&hasher.finalize());
+        RistrettoPoint::from_uniform_bytes(&arr)
+    }
+
+    fn to_arr(&self) -> GenericArray<u8, Self::ElemLen> {
+        GenericArray::clone_from_slice(self.compress().as_bytes())
+    }
+
+    fn base_point() -> Self {
+        curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT
+    }
+}
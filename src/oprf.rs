@@ -4,9 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{errors::InternalPakeError, group::Group};
+use curve25519_dalek::ristretto::RistrettoPoint;
 use digest::{BlockInput, FixedOutput, Reset, Update};
-use generic_array::{typenum::U32, ArrayLength, GenericArray};
-use hkdf::Hkdf;
+use generic_array::{typenum::Unsigned, ArrayLength, GenericArray};
+use hkdf::{Hkdf, HkdfExtract};
 use rand_core::{CryptoRng, RngCore};
 
 pub(crate) struct OprfClientBytes<Grp: Group> {
@@ -33,10 +34,64 @@ where
 {
 }
 
+/// An OPRF ciphersuite bundles the prime-order group together with the hash used for
+/// hash-to-curve and Fiat-Shamir challenges (`Hash`) and the hash used to derive the final
+/// OPRF output (`FinalHash`). This is the same shape implementations of VOPRF use to expose
+/// multiple curves behind a single suite identifier, e.g. `OPRF-ristretto255-SHA512`; a
+/// `Group` impl for a NIST curve can be added as its own `CipherSuite` without touching this
+/// trait.
+///
+/// Scope note: this only lands `OPRF-ristretto255-SHA512` below. A NIST P-256 suite needs
+/// RustCrypto's `p256`/`elliptic-curve` hash-to-curve support, which is built on a newer
+/// major version of `digest` than [`HkdfDigest`] is bound to here; pulling that in is a
+/// crate-wide dependency bump, not a `Group` impl dropped into this module, so
+/// `OPRF-P256-SHA256` is tracked as separate follow-up work rather than attempted here.
+pub trait CipherSuite {
+    /// The prime-order group the OPRF is defined over.
+    type Group: Group<UniformBytesLen = <Self::Hash as FixedOutput>::OutputSize>;
+    /// The hash used to expand inputs into uniform group elements and to derive DLEQ
+    /// challenges.
+    type Hash: HkdfDigest;
+    /// The hash used to derive the final OPRF output from the unblinded point.
+    type FinalHash: HkdfDigest;
+    /// The suite identifier mixed into the [`context_string`], e.g. `b"ristretto255-SHA512"`.
+    const SUITE_ID: &'static [u8];
+}
+
+/// The version of this crate's OPRF wire format, mixed into every [`context_string`] so that
+/// a future protocol revision can't be confused for this one.
+pub(crate) const OPRF_VERSION: u8 = 1;
+
+/// Builds the domain-separation `contextString` used to bind OPRF outputs to a protocol
+/// version and ciphersuite, following the same `"OPRF" || version || suiteId"` shape as the
+/// CFRG VOPRF draft. Without this, identical inputs hashed under different protocol versions
+/// or ciphersuites would collide.
+pub(crate) fn context_string(version: u8, suite_id: &[u8]) -> Vec<u8> {
+    let mut context = b"OPRF".to_vec();
+    context.push(version);
+    context.extend_from_slice(suite_id);
+    context
+}
+
+/// The `OPRF-ristretto255-SHA512` ciphersuite.
+pub struct Ristretto255Sha512;
+
+impl CipherSuite for Ristretto255Sha512 {
+    type Group = RistrettoPoint;
+    type Hash = sha2::Sha512;
+    type FinalHash = sha2::Sha256;
+    const SUITE_ID: &'static [u8] = b"ristretto255-SHA512";
+}
+
 /// Computes the first step for the multiplicative blinding version of DH-OPRF. This
 /// message is sent from the client (who holds the input) to the server (who holds the OPRF key).
 /// The client can also pass in an optional "pepper" string to be mixed in with the input through
-/// an HKDF computation.
+/// an HKDF computation; `pepper` occupies the HKDF salt, preserving its pre-existing role from
+/// before `context` was introduced. `context` is a [`context_string`] binding this computation
+/// to a specific protocol version and ciphersuite, preventing cross-protocol output reuse; it
+/// is folded into the same salt ahead of `pepper` (`context || pepper`) so that, like
+/// [`generate_oprf3`], both steps bind to `context` through the HKDF salt. As a result the
+/// hashed input for a given `pepper` also depends on `context`.
 pub(crate) fn generate_oprf1<
     R: RngCore + CryptoRng,
     D: HkdfDigest,
@@ -44,9 +99,16 @@ pub(crate) fn generate_oprf1<
 >(
     input: &[u8],
     pepper: Option<&[u8]>,
+    context: &[u8],
     blinding_factor_rng: &mut R,
 ) -> Result<OprfClientBytes<G>, InternalPakeError> {
-    let (hashed_input, _) = Hkdf::<D>::extract(pepper, &input);
+    let salt: Vec<u8> = match pepper {
+        Some(pepper) => [context, pepper].concat(),
+        None => context.to_vec(),
+    };
+    let mut extract_ctx = HkdfExtract::<D>::new(Some(&salt));
+    extract_ctx.input_ikm(input);
+    let (hashed_input, _) = extract_ctx.finalize();
     let blinding_factor = G::random_scalar(blinding_factor_rng);
     let alpha = G::hash_to_curve(GenericArray::from_slice(&hashed_input)) * &blinding_factor;
     Ok(OprfClientBytes {
@@ -64,19 +126,314 @@ pub(crate) fn generate_oprf2<G: Group>(
     Ok(point * oprf_key)
 }
 
+/// Converts a small nonzero integer into a group scalar, used for Shamir share indices and
+/// the Lagrange interpolation built from them.
+fn scalar_from_u64<G: Group>(value: u64) -> Result<G::Scalar, InternalPakeError> {
+    let mut bytes = vec![0u8; G::ScalarLen::to_usize()];
+    bytes[0..8].copy_from_slice(&value.to_le_bytes());
+    G::from_scalar_slice(GenericArray::from_slice(&bytes))
+}
+
+/// One key-holding server's contribution to a threshold OPRF evaluation: the nonzero share
+/// index its `oprf_key` share was evaluated at (the Shamir x-coordinate), together with
+/// `beta = alpha * k_i`, computed by that server via the ordinary [`generate_oprf2`].
+pub(crate) struct OprfKeyShare<G: Group> {
+    pub(crate) index: u64,
+    pub(crate) beta: G,
+}
+
+/// Reconstructs `beta = alpha * k` from any threshold-sized subset of per-server evaluations
+/// `beta_i = alpha * k_i`, where the `k_i` are Shamir shares of the OPRF key `k`. Computes the
+/// Lagrange coefficients `λ_i = Π_{j≠i} index_j / (index_j - index_i)` for interpolation at
+/// x = 0, then folds `Σ λ_i * beta_i`. Since no single server's share determines `k`, this
+/// removes the single point of compromise for the OPRF key: any quorum of `t`-or-more of the
+/// `n` key-holding servers can jointly answer a query, but fewer cannot.
+pub(crate) fn combine_oprf_shares<G: Group>(
+    shares: &[OprfKeyShare<G>],
+) -> Result<G, InternalPakeError> {
+    if shares.is_empty() {
+        return Err(InternalPakeError::InsufficientOprfSharesError);
+    }
+
+    let mut beta: Option<G> = None;
+    for (i, share_i) in shares.iter().enumerate() {
+        let x_i = scalar_from_u64::<G>(share_i.index)?;
+        let mut lambda_i = scalar_from_u64::<G>(1)?;
+        for (j, share_j) in shares.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let x_j = scalar_from_u64::<G>(share_j.index)?;
+            lambda_i = lambda_i * x_j * G::scalar_invert(&(x_j - x_i));
+        }
+
+        let term = share_i.beta * &lambda_i;
+        beta = Some(match beta {
+            Some(acc) => acc + term,
+            None => term,
+        });
+    }
+
+    Ok(beta.unwrap())
+}
+
+/// A non-interactive Chaum-Pedersen proof that `(G::base_point(), pkS, alpha, beta)` is a
+/// Diffie-Hellman tuple, i.e. that `beta` was computed from `alpha` using the same scalar
+/// `oprf_key` that produced `pkS = oprf_key * G::base_point()`. This lets a client verify that
+/// the server evaluated the OPRF with the key it committed to, rather than an inconsistent one.
+pub(crate) struct DleqProof<G: Group> {
+    pub(crate) challenge: G::Scalar,
+    pub(crate) response: G::Scalar,
+}
+
+/// Hashes a sequence of byte strings down to a scalar, used to derive the Fiat-Shamir
+/// challenge for the DLEQ proof below.
+fn hash_to_scalar<D: HkdfDigest, G: Group>(
+    elements: &[&[u8]],
+) -> Result<G::Scalar, InternalPakeError> {
+    let mut ikm = Vec::new();
+    for element in elements {
+        ikm.extend_from_slice(element);
+    }
+    let (digest, _) = Hkdf::<D>::extract(None, &ikm);
+    // `D`'s output may be wider than `G`'s scalar encoding (e.g. Sha512 for
+    // ristretto255-SHA512), so take only the leading `G::ScalarLen` window, sized per group
+    // rather than assumed; `from_scalar_slice` reduces it mod the group order.
+    G::from_scalar_slice(GenericArray::from_slice(&digest[..G::ScalarLen::to_usize()]))
+}
+
+/// Computes the second step for the verifiable variant of DH-OPRF (VOPRF). In addition to
+/// `beta = alpha * oprf_key`, the server returns a [`DleqProof`] over `(G::base_point(), pkS,
+/// alpha, beta)` so that a client running [`verify_oprf2`] can detect a server using an
+/// inconsistent key across invocations.
+pub(crate) fn generate_oprf2_verifiable<R: RngCore + CryptoRng, D: HkdfDigest, G: Group>(
+    point: G,
+    oprf_key: &G::Scalar,
+    rng: &mut R,
+) -> Result<(G, DleqProof<G>), InternalPakeError> {
+    let beta = point * oprf_key;
+    let pk_s = G::base_point() * oprf_key;
+
+    let r = G::random_scalar(rng);
+    let a = G::base_point() * &r;
+    let b = point * &r;
+    let challenge = hash_to_scalar::<D, G>(&[
+        &pk_s.to_arr(),
+        &point.to_arr(),
+        &beta.to_arr(),
+        &a.to_arr(),
+        &b.to_arr(),
+    ])?;
+    let response = r - challenge * oprf_key;
+
+    Ok((beta, DleqProof {
+        challenge,
+        response,
+    }))
+}
+
+/// Verifies a [`DleqProof`] produced by [`generate_oprf2_verifiable`], confirming that `beta`
+/// was computed from `alpha` using the same scalar committed to by `pkS`.
+pub(crate) fn verify_oprf2<D: HkdfDigest, G: Group>(
+    point: G,
+    beta: G,
+    pk_s: G,
+    proof: &DleqProof<G>,
+) -> Result<(), InternalPakeError> {
+    let a_prime = G::base_point() * &proof.response + pk_s * &proof.challenge;
+    let b_prime = point * &proof.response + beta * &proof.challenge;
+    let challenge_prime = hash_to_scalar::<D, G>(&[
+        &pk_s.to_arr(),
+        &point.to_arr(),
+        &beta.to_arr(),
+        &a_prime.to_arr(),
+        &b_prime.to_arr(),
+    ])?;
+
+    if challenge_prime == proof.challenge {
+        Ok(())
+    } else {
+        Err(InternalPakeError::DleqProofVerificationError)
+    }
+}
+
+/// Derives the per-element seeds `d_i = H(pkS, alpha_1, beta_1, ..., alpha_n, beta_n, i)` used
+/// to form the random linear combination for a batched DLEQ proof.
+fn batch_seeds<D: HkdfDigest, G: Group>(
+    pk_s: &G,
+    points: &[G],
+    betas: &[G],
+) -> Result<Vec<G::Scalar>, InternalPakeError> {
+    let mut transcript = Vec::new();
+    transcript.extend_from_slice(&pk_s.to_arr());
+    for (point, beta) in points.iter().zip(betas.iter()) {
+        transcript.extend_from_slice(&point.to_arr());
+        transcript.extend_from_slice(&beta.to_arr());
+    }
+
+    (0..points.len())
+        .map(|i| hash_to_scalar::<D, G>(&[&transcript, &(i as u64).to_be_bytes()]))
+        .collect()
+}
+
+/// Folds a batch of blinded inputs and server responses into the composites
+/// `M = Σ d_i * alpha_i` and `Z = Σ d_i * beta_i` used by the batched DLEQ proof. Errors on
+/// an empty batch, or on a `points`/`betas` length mismatch, rather than panicking.
+fn batch_composite<G: Group>(
+    points: &[G],
+    betas: &[G],
+    seeds: &[G::Scalar],
+) -> Result<(G, G), InternalPakeError> {
+    if points.is_empty() {
+        return Err(InternalPakeError::EmptyBatchError);
+    }
+    if points.len() != betas.len() {
+        return Err(InternalPakeError::BatchLengthMismatchError);
+    }
+
+    let mut m: Option<G> = None;
+    let mut z: Option<G> = None;
+    for i in 0..points.len() {
+        let m_term = points[i] * &seeds[i];
+        let z_term = betas[i] * &seeds[i];
+        m = Some(match m {
+            Some(acc) => acc + m_term,
+            None => m_term,
+        });
+        z = Some(match z {
+            Some(acc) => acc + z_term,
+            None => z_term,
+        });
+    }
+    Ok((m.unwrap(), z.unwrap()))
+}
+
+/// Computes a batched server response for `n` blinded inputs `[alpha_1..alpha_n]`, returning
+/// `[beta_1..beta_n]` together with a *single* [`DleqProof`] covering the whole batch. Rather
+/// than proving each `(G::base_point(), pkS, alpha_i, beta_i)` tuple independently, this derives
+/// per-element seeds and proves the random linear combination `(G::base_point(), pkS, M, Z)`
+/// instead, so the proof stays one `(challenge, response)` pair and is checked with one DLEQ
+/// verification however large the batch is. Deriving the seeds and composites still takes
+/// work for every element of the batch.
+pub(crate) fn generate_oprf2_batch<R: RngCore + CryptoRng, D: HkdfDigest, G: Group>(
+    points: &[G],
+    oprf_key: &G::Scalar,
+    rng: &mut R,
+) -> Result<(Vec<G>, DleqProof<G>), InternalPakeError> {
+    let pk_s = G::base_point() * oprf_key;
+    let betas: Vec<G> = points.iter().map(|point| *point * oprf_key).collect();
+
+    let seeds = batch_seeds::<D, G>(&pk_s, points, &betas)?;
+    let (m, z) = batch_composite(points, &betas, &seeds)?;
+
+    let r = G::random_scalar(rng);
+    let a = G::base_point() * &r;
+    let b = m * &r;
+    let challenge = hash_to_scalar::<D, G>(&[
+        &pk_s.to_arr(),
+        &m.to_arr(),
+        &z.to_arr(),
+        &a.to_arr(),
+        &b.to_arr(),
+    ])?;
+    let response = r - challenge * oprf_key;
+
+    Ok((betas, DleqProof {
+        challenge,
+        response,
+    }))
+}
+
+/// Verifies a batched [`DleqProof`] produced by [`generate_oprf2_batch`], reconstructing the
+/// same composites `M = Σ d_i * alpha_i` and `Z = Σ d_i * beta_i` and checking the one proof.
+pub(crate) fn verify_oprf2_batch<D: HkdfDigest, G: Group>(
+    points: &[G],
+    betas: &[G],
+    pk_s: G,
+    proof: &DleqProof<G>,
+) -> Result<(), InternalPakeError> {
+    let seeds = batch_seeds::<D, G>(&pk_s, points, betas)?;
+    let (m, z) = batch_composite(points, betas, &seeds)?;
+
+    let a_prime = G::base_point() * &proof.response + pk_s * &proof.challenge;
+    let b_prime = m * &proof.response + z * &proof.challenge;
+    let challenge_prime = hash_to_scalar::<D, G>(&[
+        &pk_s.to_arr(),
+        &m.to_arr(),
+        &z.to_arr(),
+        &a_prime.to_arr(),
+        &b_prime.to_arr(),
+    ])?;
+
+    if challenge_prime == proof.challenge {
+        Ok(())
+    } else {
+        Err(InternalPakeError::DleqProofVerificationError)
+    }
+}
+
 /// Computes the third step for the multiplicative blinding version of DH-OPRF, in which
-/// the client unblinds the server's message.
-pub(crate) fn generate_oprf3<G: Group>(
+/// the client unblinds the server's message. The finalization hash `D` (and with it the
+/// output length) is carried by the ciphersuite rather than fixed to SHA-256, so this can
+/// back any `OPRF-<curve>-<hash>` suite, not just ristretto255-SHA512. `context` must match
+/// the [`context_string`] passed to [`generate_oprf1`] for this computation. Like
+/// [`generate_oprf1`], this streams `unblinded`'s encoding and `input` into an incremental
+/// [`HkdfExtract`] rather than concatenating them into a `Vec` first, so callers can pass an
+/// arbitrarily large `input` (e.g. a file) without materializing it alongside the point.
+pub(crate) fn generate_oprf3<D: HkdfDigest, G: Group>(
     input: &[u8],
     point: G,
     blinding_factor: &G::Scalar,
-) -> Result<GenericArray<u8, U32>, InternalPakeError> {
+    context: &[u8],
+) -> Result<GenericArray<u8, D::OutputSize>, InternalPakeError> {
     let unblinded = point * &G::scalar_invert(&blinding_factor);
-    let ikm: Vec<u8> = [&unblinded.to_arr()[..], input].concat();
-    let (prk, _) = Hkdf::<sha2::Sha256>::extract(None, &ikm);
+    let mut extract_ctx = HkdfExtract::<D>::new(Some(context));
+    extract_ctx.input_ikm(&unblinded.to_arr());
+    extract_ctx.input_ikm(input);
+    let (prk, _) = extract_ctx.finalize();
     Ok(prk)
 }
 
+/// Computes the third step of the verifiable variant of DH-OPRF, in which the client first
+/// checks the server's [`DleqProof`] via [`verify_oprf2`] and only unblinds `beta` once the
+/// proof confirms that it was produced with the key committed to by `pkS`. `D` is the hash
+/// used for the DLEQ challenge; `F` is the finalization hash used to derive the output.
+pub(crate) fn generate_oprf3_verifiable<D: HkdfDigest, F: HkdfDigest, G: Group>(
+    input: &[u8],
+    alpha: G,
+    beta: G,
+    pk_s: G,
+    blinding_factor: &G::Scalar,
+    proof: &DleqProof<G>,
+    context: &[u8],
+) -> Result<GenericArray<u8, F::OutputSize>, InternalPakeError> {
+    verify_oprf2::<D, G>(alpha, beta, pk_s, proof)?;
+    generate_oprf3::<F, G>(input, beta, blinding_factor, context)
+}
+
+/// The [`CipherSuite`]-aware counterpart to [`generate_oprf1`]: hashes and blinds `input`
+/// using the suite's `Hash` and binds it to the suite's own [`context_string`]. Step 3 is
+/// [`generate_oprf3_suite`].
+pub(crate) fn generate_oprf1_suite<C: CipherSuite, R: RngCore + CryptoRng>(
+    input: &[u8],
+    pepper: Option<&[u8]>,
+    blinding_factor_rng: &mut R,
+) -> Result<OprfClientBytes<C::Group>, InternalPakeError> {
+    let context = context_string(OPRF_VERSION, C::SUITE_ID);
+    generate_oprf1::<_, C::Hash, C::Group>(input, pepper, &context, blinding_factor_rng)
+}
+
+/// The [`CipherSuite`]-aware counterpart to [`generate_oprf3`]: unblinds `point` and derives
+/// the final output using the suite's finalization hash and [`context_string`].
+pub(crate) fn generate_oprf3_suite<C: CipherSuite>(
+    input: &[u8],
+    point: C::Group,
+    blinding_factor: &<C::Group as Group>::Scalar,
+) -> Result<GenericArray<u8, <C::FinalHash as FixedOutput>::OutputSize>, InternalPakeError> {
+    let context = context_string(OPRF_VERSION, C::SUITE_ID);
+    generate_oprf3::<C::FinalHash, C::Group>(input, point, blinding_factor, &context)
+}
+
 // Tests
 // =====
 
@@ -84,24 +441,25 @@ pub(crate) fn generate_oprf3<G: Group>(
 mod tests {
     use super::*;
     use crate::group::Group;
-    use curve25519_dalek::ristretto::RistrettoPoint;
     use generic_array::{arr, GenericArray};
     use hkdf::Hkdf;
     use rand_core::OsRng;
     use sha2::{Digest, Sha256, Sha512};
 
+    const TEST_CONTEXT: &[u8] = b"test-context";
+
     fn prf(
         input: &[u8],
         oprf_key: &[u8; 32],
     ) -> GenericArray<u8, <RistrettoPoint as Group>::ElemLen> {
-        let (hashed_input, _) = Hkdf::<Sha512>::extract(None, &input);
+        let (hashed_input, _) = Hkdf::<Sha512>::extract(Some(TEST_CONTEXT), input);
         let point = RistrettoPoint::hash_to_curve(GenericArray::from_slice(&hashed_input));
         let scalar =
             RistrettoPoint::from_scalar_slice(GenericArray::from_slice(&oprf_key[..])).unwrap();
         let res = point * scalar;
         let ikm: Vec<u8> = [&res.to_arr()[..], &input].concat();
 
-        let (prk, _) = Hkdf::<Sha256>::extract(None, &ikm);
+        let (prk, _) = Hkdf::<Sha256>::extract(Some(TEST_CONTEXT), &ikm);
         prk
     }
 
@@ -112,14 +470,15 @@ mod tests {
         let OprfClientBytes {
             alpha,
             blinding_factor,
-        } = generate_oprf1::<_, Sha512, RistrettoPoint>(&input[..], None, &mut rng)?;
+        } = generate_oprf1::<_, Sha512, RistrettoPoint>(&input[..], None, TEST_CONTEXT, &mut rng)?;
         let salt_bytes = arr![
             u8; 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
             24, 25, 26, 27, 28, 29, 30, 31, 32,
         ];
         let salt = RistrettoPoint::from_scalar_slice(&salt_bytes)?;
         let beta = generate_oprf2::<RistrettoPoint>(alpha, &salt)?;
-        let res = generate_oprf3::<RistrettoPoint>(input, beta, &blinding_factor)?;
+        let res =
+            generate_oprf3::<Sha256, RistrettoPoint>(input, beta, &blinding_factor, TEST_CONTEXT)?;
         let res2 = prf(&input[..], &salt.as_bytes());
         assert_eq!(res, res2);
         Ok(())
@@ -133,10 +492,13 @@ mod tests {
         let OprfClientBytes {
             alpha,
             blinding_factor,
-        } = generate_oprf1::<_, Sha512, RistrettoPoint>(&input, None, &mut rng).unwrap();
-        let res = generate_oprf3::<RistrettoPoint>(&input, alpha, &blinding_factor).unwrap();
+        } = generate_oprf1::<_, Sha512, RistrettoPoint>(&input, None, TEST_CONTEXT, &mut rng)
+            .unwrap();
+        let res =
+            generate_oprf3::<Sha256, RistrettoPoint>(&input, alpha, &blinding_factor, TEST_CONTEXT)
+                .unwrap();
 
-        let (hashed_input, _) = Hkdf::<Sha512>::extract(None, &input);
+        let (hashed_input, _) = Hkdf::<Sha512>::extract(Some(TEST_CONTEXT), &input);
 
         // This is because RistrettoPoint is on an obsolete sha2 version
         let mut bits = [0u8; 64];
@@ -148,8 +510,149 @@ mod tests {
         let mut ikm: Vec<u8> = Vec::new();
         ikm.extend_from_slice(&point.to_arr());
         ikm.extend_from_slice(&input);
-        let (prk, _) = Hkdf::<Sha256>::extract(None, &ikm);
+        let (prk, _) = Hkdf::<Sha256>::extract(Some(TEST_CONTEXT), &ikm);
 
         assert_eq!(res, prk);
     }
+
+    #[test]
+    fn oprf_verifiable_retrieval() -> Result<(), InternalPakeError> {
+        let input = b"hunter2";
+        let mut rng = OsRng;
+        let OprfClientBytes {
+            alpha,
+            blinding_factor,
+        } = generate_oprf1::<_, Sha512, RistrettoPoint>(&input[..], None, TEST_CONTEXT, &mut rng)?;
+        let oprf_key = RistrettoPoint::random_scalar(&mut rng);
+        let pk_s = RistrettoPoint::base_point() * &oprf_key;
+
+        let (beta, proof) =
+            generate_oprf2_verifiable::<_, Sha512, RistrettoPoint>(alpha, &oprf_key, &mut rng)?;
+        let res = generate_oprf3_verifiable::<Sha512, Sha256, RistrettoPoint>(
+            input,
+            alpha,
+            beta,
+            pk_s,
+            &blinding_factor,
+            &proof,
+            TEST_CONTEXT,
+        )?;
+        let res2 =
+            generate_oprf3::<Sha256, RistrettoPoint>(input, beta, &blinding_factor, TEST_CONTEXT)?;
+        assert_eq!(res, res2);
+        Ok(())
+    }
+
+    #[test]
+    fn oprf_verifiable_rejects_inconsistent_key() -> Result<(), InternalPakeError> {
+        let input = b"hunter2";
+        let mut rng = OsRng;
+        let OprfClientBytes { alpha, .. } =
+            generate_oprf1::<_, Sha512, RistrettoPoint>(&input[..], None, TEST_CONTEXT, &mut rng)?;
+        let oprf_key = RistrettoPoint::random_scalar(&mut rng);
+        let other_key = RistrettoPoint::random_scalar(&mut rng);
+        let pk_s = RistrettoPoint::base_point() * &other_key;
+
+        let (beta, proof) =
+            generate_oprf2_verifiable::<_, Sha512, RistrettoPoint>(alpha, &oprf_key, &mut rng)?;
+        let result = verify_oprf2::<Sha512, RistrettoPoint>(alpha, beta, pk_s, &proof);
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn oprf_verifiable_batch() -> Result<(), InternalPakeError> {
+        let mut rng = OsRng;
+        let inputs: Vec<&[u8]> = vec![b"hunter2", b"correct horse battery staple", b"swordfish"];
+        let oprf_key = RistrettoPoint::random_scalar(&mut rng);
+        let pk_s = RistrettoPoint::base_point() * &oprf_key;
+
+        let mut alphas = Vec::new();
+        let mut blinding_factors = Vec::new();
+        for input in &inputs {
+            let OprfClientBytes {
+                alpha,
+                blinding_factor,
+            } = generate_oprf1::<_, Sha512, RistrettoPoint>(input, None, TEST_CONTEXT, &mut rng)?;
+            alphas.push(alpha);
+            blinding_factors.push(blinding_factor);
+        }
+
+        let (betas, proof) =
+            generate_oprf2_batch::<_, Sha512, RistrettoPoint>(&alphas, &oprf_key, &mut rng)?;
+        verify_oprf2_batch::<Sha512, RistrettoPoint>(&alphas, &betas, pk_s, &proof)?;
+
+        for i in 0..inputs.len() {
+            let res = generate_oprf3::<Sha256, RistrettoPoint>(
+                inputs[i],
+                betas[i],
+                &blinding_factors[i],
+                TEST_CONTEXT,
+            )?;
+            let res2 = generate_oprf3::<Sha256, RistrettoPoint>(
+                inputs[i],
+                generate_oprf2::<RistrettoPoint>(alphas[i], &oprf_key)?,
+                &blinding_factors[i],
+                TEST_CONTEXT,
+            )?;
+            assert_eq!(res, res2);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn oprf_ristretto255_sha512_suite() -> Result<(), InternalPakeError> {
+        let input = b"hunter2";
+        let mut rng = OsRng;
+        let OprfClientBytes {
+            alpha,
+            blinding_factor,
+        } = generate_oprf1_suite::<Ristretto255Sha512, _>(&input[..], None, &mut rng)?;
+        let oprf_key = RistrettoPoint::random_scalar(&mut rng);
+        let beta = generate_oprf2::<RistrettoPoint>(alpha, &oprf_key)?;
+        let res = generate_oprf3_suite::<Ristretto255Sha512>(input, beta, &blinding_factor)?;
+        let context = context_string(OPRF_VERSION, Ristretto255Sha512::SUITE_ID);
+        let res2 =
+            generate_oprf3::<Sha256, RistrettoPoint>(input, beta, &blinding_factor, &context)?;
+        assert_eq!(res, res2);
+        Ok(())
+    }
+
+    #[test]
+    fn oprf_threshold_combine() -> Result<(), InternalPakeError> {
+        let mut rng = OsRng;
+        let input = b"hunter2";
+        let OprfClientBytes { alpha, .. } =
+            generate_oprf1::<_, Sha512, RistrettoPoint>(&input[..], None, TEST_CONTEXT, &mut rng)?;
+
+        // 2-of-3 Shamir split of the OPRF key via the degree-1 polynomial f(x) = secret + coeff*x.
+        let secret = RistrettoPoint::random_scalar(&mut rng);
+        let coeff = RistrettoPoint::random_scalar(&mut rng);
+        let share_key = |index: u64| -> Result<_, InternalPakeError> {
+            Ok(secret + coeff * scalar_from_u64::<RistrettoPoint>(index)?)
+        };
+
+        let mut shares = Vec::new();
+        for index in 1..=3u64 {
+            let beta = generate_oprf2::<RistrettoPoint>(alpha, &share_key(index)?)?;
+            shares.push(OprfKeyShare { index, beta });
+        }
+
+        let expected = generate_oprf2::<RistrettoPoint>(alpha, &secret)?;
+
+        // Any 2-of-3 subset reconstructs the same beta as evaluating with the full secret.
+        let subset_a = vec![
+            OprfKeyShare { index: shares[0].index, beta: shares[0].beta },
+            OprfKeyShare { index: shares[1].index, beta: shares[1].beta },
+        ];
+        assert_eq!(combine_oprf_shares(&subset_a)?.to_arr(), expected.to_arr());
+
+        let subset_b = vec![
+            OprfKeyShare { index: shares[0].index, beta: shares[0].beta },
+            OprfKeyShare { index: shares[2].index, beta: shares[2].beta },
+        ];
+        assert_eq!(combine_oprf_shares(&subset_b)?.to_arr(), expected.to_arr());
+
+        Ok(())
+    }
 }